@@ -1,15 +1,43 @@
+#[cfg(not(feature = "rapier"))]
 use bevy::math::bounding::{Aabb2d, BoundingCircle, BoundingVolume, IntersectsVolume};
 use bevy::prelude::*;
 use bevy::sprite::MaterialMesh2dBundle;
 
+#[cfg(feature = "rapier")]
+mod physics_rapier;
+
 const BALL_SIZE: f32 = 5.;
+const BALL_SPEED: f32 = 250.;
 
-const PADDLE_SPEED: f32 = 1.;
+const PADDLE_SPEED: f32 = 300.;
 const PADDLE_WIDTH: f32 = 10.;
 const PADDLE_HEIGHT: f32 = 50.;
 
 const GUTTER_HEIGHT: f32 = 20.;
 
+// Run gameplay at a constant rate so motion and collisions are identical
+// regardless of the monitor's refresh rate.
+const TIME_STEP: f32 = 1. / 60.;
+
+// The steepest angle (from horizontal) a paddle can send the ball at when
+// it's hit right at the edge.
+#[cfg(not(feature = "rapier"))]
+const MAX_BOUNCE_ANGLE: f32 = 60. * std::f32::consts::PI / 180.;
+
+// First side to reach this many points wins the match.
+const WINNING_SCORE: u32 = 11;
+
+// Each paddle hit multiplies the ball's speed by this factor, up to
+// `MAX_BALL_SPEED`, so long rallies ramp up the pace.
+#[cfg(not(feature = "rapier"))]
+const RALLY_SPEED_UP: f32 = 1.05;
+#[cfg(not(feature = "rapier"))]
+const MAX_BALL_SPEED: f32 = 600.;
+
+// How long the ball sits still after a point before it's relaunched.
+const SERVE_DELAY: f32 = 1.;
+
+#[cfg(not(feature = "rapier"))]
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 enum Collision {
     Left,
@@ -17,6 +45,8 @@ enum Collision {
     Top,
     Bottom,
 }
+
+#[derive(Debug, Clone, Copy)]
 enum Scorer {
     Ai,
     Player,
@@ -25,12 +55,70 @@ enum Scorer {
 #[derive(Event)]
 struct Scored(Scorer);
 
+#[derive(States, Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+enum AppState {
+    #[default]
+    MainMenu,
+    Serving,
+    Playing,
+    GameOver,
+}
+
 #[derive(Resource, Default)]
 struct Score {
     player: u32,
     ai: u32,
 }
 
+// Counts consecutive paddle hits since the ball was last served, driving the
+// speed ramp in `handle_collisions`.
+#[derive(Resource, Default)]
+struct Rally {
+    hits: u32,
+}
+
+#[derive(Component)]
+struct RallyText;
+
+// Who scored the last point, used to serve the ball back towards them.
+#[derive(Resource, Default)]
+struct LastScorer(Option<Scorer>);
+
+// Counts down the pause between a point being scored and the ball
+// relaunching.
+#[derive(Resource)]
+struct ServeTimer(Timer);
+
+impl Default for ServeTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(SERVE_DELAY, TimerMode::Once))
+    }
+}
+
+// Gameplay moments that should make a sound. Keeping this as an event
+// decouples `play_audio` from the collision/scoring logic, the same way
+// `Scored` decouples scorekeeping from `detect_scoring`.
+#[derive(Event)]
+enum GameAudio {
+    WallBounce,
+    PaddleHit { ball_speed: f32 },
+    Score,
+}
+
+// Handles to the sound clips, loaded once at startup.
+#[derive(Resource)]
+struct AudioAssets {
+    wall_bounce: Handle<AudioSource>,
+    paddle_hit: Handle<AudioSource>,
+    score: Handle<AudioSource>,
+}
+
+#[derive(Component)]
+struct MainMenuUi;
+
+#[derive(Component)]
+struct GameOverUi;
+
 #[derive(Component)]
 struct PlayerScore;
 
@@ -40,6 +128,11 @@ struct AiScore;
 #[derive(Component)]
 struct Position(Vec2);
 
+// Holds the `Position` from the previous `FixedUpdate` tick so
+// `project_positions` can interpolate smooth motion between fixed steps.
+#[derive(Component, Default)]
+struct PreviousPosition(Vec2);
+
 // This component is a tuple type, we can access the Vec2 it holds
 // by using the position of the item in the tuple
 // e.g. velocity.0 which would be a Vec2
@@ -64,6 +157,7 @@ struct BallBundle {
     shape: Shape,
     velocity: Velocity,
     position: Position,
+    previous_position: PreviousPosition,
 }
 
 impl BallBundle {
@@ -71,8 +165,9 @@ impl BallBundle {
         Self {
             ball: Ball,
             shape: Shape(Vec2::new(BALL_SIZE, BALL_SIZE)),
-            velocity: Velocity(Vec2::new(x, y)),
+            velocity: Velocity(Vec2::new(x, y) * BALL_SPEED),
             position: Position(Vec2::new(0., 0.)),
+            previous_position: PreviousPosition(Vec2::new(0., 0.)),
         }
     }
 }
@@ -86,6 +181,7 @@ struct PaddleBundle {
     shape: Shape,
     position: Position,
     velocity: Velocity,
+    previous_position: PreviousPosition,
 }
 
 impl PaddleBundle {
@@ -95,6 +191,7 @@ impl PaddleBundle {
             shape: Shape(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
             position: Position(Vec2::new(x, y)),
             velocity: Velocity(Vec2::new(0., 0.)),
+            previous_position: PreviousPosition(Vec2::new(x, y)),
         }
     }
 }
@@ -117,31 +214,93 @@ impl GutterBundle {
     }
 }
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins)
+    let mut app = App::new();
+
+    app.add_plugins(DefaultPlugins)
+        .insert_resource(Time::<Fixed>::from_seconds(TIME_STEP as f64))
+        .init_state::<AppState>()
         .init_resource::<Score>()
+        .init_resource::<Rally>()
+        .init_resource::<LastScorer>()
+        .init_resource::<ServeTimer>()
         .add_event::<Scored>()
+        .add_event::<GameAudio>()
         .add_systems(Startup, spawn_ball)
         .add_systems(Startup, spawn_camera)
         .add_systems(Startup, spawn_paddles)
         .add_systems(Startup, spawn_gutters)
         .add_systems(Startup, spawn_scoreboard)
-        .add_systems(Update, move_ball)
-        .add_systems(Update, handle_player_input)
-        .add_systems(Update, detect_scoring)
-        .add_systems(Update, move_ai)
-        .add_systems(Update, reset_ball.after(detect_scoring))
-        .add_systems(Update, update_score.after(detect_scoring))
-        // Add our projection system to run after
-        // we move our ball so we are not reading
-        // movement one frame behind
-        .add_systems(Update, project_positions.after(move_ball))
-        .add_systems(Update, handle_collisions.after(move_ball))
-        .add_systems(Update, move_paddles.after(handle_player_input))
+        .add_systems(Startup, load_audio_assets)
+        .add_systems(Update, play_audio)
+        .add_systems(OnEnter(AppState::MainMenu), spawn_main_menu_screen)
+        .add_systems(OnExit(AppState::MainMenu), despawn_main_menu_screen)
+        .add_systems(OnEnter(AppState::Serving), enter_serving)
+        .add_systems(OnEnter(AppState::GameOver), spawn_game_over_screen)
+        .add_systems(OnExit(AppState::GameOver), despawn_game_over_screen)
+        .add_systems(
+            Update,
+            handle_main_menu_input.run_if(in_state(AppState::MainMenu)),
+        )
+        .add_systems(Update, launch_ball.run_if(in_state(AppState::Serving)))
+        .add_systems(
+            Update,
+            handle_game_over_input.run_if(in_state(AppState::GameOver)),
+        )
+        .add_systems(
+            Update,
+            handle_player_input.run_if(in_state(AppState::Playing)),
+        )
+        // Motion and collision run on a fixed 60 Hz schedule so gameplay is
+        // deterministic regardless of the display's refresh rate. They're
+        // gated to `Playing` so nothing moves during the menu, serve pause,
+        // or game-over screen.
+        .add_systems(
+            FixedUpdate,
+            store_previous_positions
+                .before(move_paddles)
+                .before(move_ai)
+                .run_if(in_state(AppState::Playing)),
+        )
+        .add_systems(FixedUpdate, move_ai.run_if(in_state(AppState::Playing)))
+        .add_systems(
+            FixedUpdate,
+            move_paddles
+                .after(handle_player_input)
+                .run_if(in_state(AppState::Playing)),
+        )
+        .add_systems(FixedUpdate, update_score)
+        // Interpolate rendered `Transform`s between fixed steps so motion
+        // looks smooth at any frame rate.
+        .add_systems(Update, project_positions)
         .add_systems(Update, update_scoreboard.after(update_score))
-        .run();
+        .add_systems(Update, update_rally_text);
+
+    // The `rapier` feature swaps the bespoke penetration-depth collision
+    // path below for a `bevy_rapier2d` rigid-body simulation; see
+    // `physics_rapier` for how ball motion and scoring are driven there.
+    #[cfg(feature = "rapier")]
+    app.add_plugins(physics_rapier::RapierPongPlugin);
+
+    #[cfg(not(feature = "rapier"))]
+    app.add_systems(
+        FixedUpdate,
+        (move_ball, handle_collisions, detect_scoring)
+            .chain()
+            .after(store_previous_positions)
+            .run_if(in_state(AppState::Playing)),
+    );
+
+    app.run();
+}
+
+fn store_previous_positions(mut positionable: Query<(&mut PreviousPosition, &Position)>) {
+    for (mut previous_position, position) in &mut positionable {
+        previous_position.0 = position.0;
+    }
 }
 
+// `ball` is only read when the `rapier` feature is on.
+#[allow(unused_variables)]
 fn spawn_ball(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -161,32 +320,93 @@ fn spawn_ball(
     // Here we are using `spawn` instead of `spawn_empty`
     // followed by an `insert`. They mean the same thing,
     // letting us spawn many components on a new entity at once.
-    commands.spawn((
-        BallBundle::new(1., 1.),
-        MaterialMesh2dBundle {
-            mesh: mesh_handle.into(),
-            material: material_handle,
-            ..default()
-        },
-    ));
+    let ball = commands
+        .spawn((
+            BallBundle::new(1., 1.),
+            MaterialMesh2dBundle {
+                mesh: mesh_handle.into(),
+                material: material_handle,
+                ..default()
+            },
+        ))
+        .id();
+
+    #[cfg(feature = "rapier")]
+    commands.entity(ball).insert(physics_rapier::ball_physics());
 }
 fn spawn_camera(mut commands: Commands) {
     commands.spawn_empty().insert(Camera2dBundle::default());
 }
 
-fn project_positions(mut positionable: Query<(&mut Transform, &Position)>) {
-    for (mut transform, position) in &mut positionable {
-        transform.translation = position.0.extend(0.);
+fn load_audio_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AudioAssets {
+        wall_bounce: asset_server.load("audio/wall_bounce.wav"),
+        paddle_hit: asset_server.load("audio/paddle_hit.wav"),
+        score: asset_server.load("audio/score.wav"),
+    });
+}
+
+fn play_audio(
+    mut events: EventReader<GameAudio>,
+    mut commands: Commands,
+    audio_assets: Res<AudioAssets>,
+) {
+    for event in events.read() {
+        let (source, speed) = match event {
+            GameAudio::WallBounce => (audio_assets.wall_bounce.clone(), 1.),
+            // Faster rallies pitch the paddle hit up a little so they feel
+            // more intense.
+            GameAudio::PaddleHit { ball_speed } => (
+                audio_assets.paddle_hit.clone(),
+                (ball_speed / BALL_SPEED).clamp(0.75, 1.5),
+            ),
+            GameAudio::Score => (audio_assets.score.clone(), 1.),
+        };
+
+        commands.spawn(AudioBundle {
+            source,
+            settings: PlaybackSettings::DESPAWN.with_speed(speed),
+        });
     }
 }
+
+// Under the `rapier` feature the ball's `Transform` is driven by the physics
+// simulation itself, not by `Position`/`PreviousPosition` (which the ball
+// never updates in that configuration) — exclude it here so this system
+// doesn't stomp the simulated transform back to the ball's stale position.
+fn project_positions(
+    fixed_time: Res<Time<Fixed>>,
+    #[cfg(not(feature = "rapier"))] mut positionable: Query<(
+        &mut Transform,
+        &Position,
+        Option<&PreviousPosition>,
+    )>,
+    #[cfg(feature = "rapier")] mut positionable: Query<
+        (&mut Transform, &Position, Option<&PreviousPosition>),
+        Without<Ball>,
+    >,
+) {
+    let alpha = fixed_time.overstep_fraction();
+
+    for (mut transform, position, previous_position) in &mut positionable {
+        let translation = match previous_position {
+            Some(previous_position) => previous_position.0.lerp(position.0, alpha),
+            None => position.0,
+        };
+        transform.translation = translation.extend(0.);
+    }
+}
+#[cfg(not(feature = "rapier"))]
 fn move_ball(
     // Give me all positions that also contain a `Ball` component
     mut ball: Query<(&mut Position, &Velocity), With<Ball>>,
 ) {
     if let Ok((mut position, velocity)) = ball.get_single_mut() {
-        position.0 += velocity.0
+        position.0 += velocity.0 * TIME_STEP
     }
 }
+// `player_paddle`/`ai_paddle` are only read when the `rapier` feature is on.
+#[allow(unused_variables)]
 fn spawn_paddles(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -205,80 +425,162 @@ fn spawn_paddles(
 
         let mesh_handle = meshes.add(mesh);
 
-        commands.spawn((
-            Player,
-            PaddleBundle::new(right_paddle_x, 0.),
-            MaterialMesh2dBundle {
-                mesh: mesh_handle.clone().into(),
-                material: materials.add(ColorMaterial::from(Color::srgb(0., 1., 0.))),
-                ..default()
-            },
-        ));
-
-        commands.spawn((
-            Ai,
-            PaddleBundle::new(left_paddle_x, 0.),
-            MaterialMesh2dBundle {
-                mesh: mesh_handle.into(),
-                material: materials.add(ColorMaterial::from(Color::srgb(0., 0., 1.))),
-                ..default()
-            },
-        ));
+        let player_paddle = commands
+            .spawn((
+                Player,
+                PaddleBundle::new(right_paddle_x, 0.),
+                MaterialMesh2dBundle {
+                    mesh: mesh_handle.clone().into(),
+                    material: materials.add(ColorMaterial::from(Color::srgb(0., 1., 0.))),
+                    ..default()
+                },
+            ))
+            .id();
+
+        let ai_paddle = commands
+            .spawn((
+                Ai,
+                PaddleBundle::new(left_paddle_x, 0.),
+                MaterialMesh2dBundle {
+                    mesh: mesh_handle.into(),
+                    material: materials.add(ColorMaterial::from(Color::srgb(0., 0., 1.))),
+                    ..default()
+                },
+            ))
+            .id();
+
+        #[cfg(feature = "rapier")]
+        {
+            commands
+                .entity(player_paddle)
+                .insert(physics_rapier::paddle_physics());
+            commands
+                .entity(ai_paddle)
+                .insert(physics_rapier::paddle_physics());
+        }
     }
 }
-// Returns `Some` if `ball` collides with `wall`
-// The returned `Collision` is the side of `wall`
-// that the `ball` hit.
-fn collide_with_side(ball: BoundingCircle, wall: Aabb2d) -> Option<Collision> {
+// Returns `Some` if `ball` collides with `wall`. The returned `Collision` is
+// the side of `wall` that the `ball` hit, and the `f32` is how far the ball
+// has penetrated `wall` along that side's axis.
+#[cfg(not(feature = "rapier"))]
+fn collide_with_side(ball: BoundingCircle, wall: Aabb2d) -> Option<(Collision, f32)> {
     if !ball.intersects(&wall) {
         return None;
     }
 
-    let closest_point = wall.closest_point(ball.center());
-    let offset = ball.center() - closest_point;
-
-    let side = if offset.x.abs() > offset.y.abs() {
-        if offset.x < 0. {
-            Collision::Left
+    let ball_min = ball.center() - Vec2::splat(ball.radius());
+    let ball_max = ball.center() + Vec2::splat(ball.radius());
+
+    // Overlap along each axis. The axis with the *smallest* overlap is the
+    // one the ball actually crossed, so it's the correct collision normal
+    // even when the ball has tunnelled deep into a corner.
+    let x_depth = (ball_max.x - wall.min.x).min(wall.max.x - ball_min.x);
+    let y_depth = (ball_max.y - wall.min.y).min(wall.max.y - ball_min.y);
+
+    // Which side was hit is which side the ball's center is on relative to
+    // the wall's *midpoint* on that axis, not the near edge — once the ball
+    // has tunnelled past the edge (the very case this depth-based approach
+    // exists to handle) comparing against the edge picks the wrong side.
+    let (side, depth) = if x_depth < y_depth {
+        if ball.center().x < wall.min.x.midpoint(wall.max.x) {
+            (Collision::Left, x_depth)
         } else {
-            Collision::Right
+            (Collision::Right, x_depth)
         }
-    } else if offset.y > 0. {
-        Collision::Top
+    } else if ball.center().y > wall.min.y.midpoint(wall.max.y) {
+        (Collision::Top, y_depth)
     } else {
-        Collision::Bottom
+        (Collision::Bottom, y_depth)
     };
 
-    Some(side)
+    Some((side, depth))
+}
+// The outward-facing normal for the side of a wall that was hit, i.e. the
+// direction the ball should be pushed to leave the wall.
+#[cfg(not(feature = "rapier"))]
+fn collision_normal(collision: Collision) -> Vec2 {
+    match collision {
+        Collision::Left => Vec2::new(-1., 0.),
+        Collision::Right => Vec2::new(1., 0.),
+        Collision::Top => Vec2::new(0., 1.),
+        Collision::Bottom => Vec2::new(0., -1.),
+    }
 }
+#[cfg(not(feature = "rapier"))]
 fn handle_collisions(
-    mut ball: Query<(&mut Velocity, &Position, &Shape), With<Ball>>,
-    other_things: Query<(&Position, &Shape), Without<Ball>>,
+    mut ball: Query<(&mut Velocity, &mut Position, &Shape), With<Ball>>,
+    paddles: Query<(&Position, &Shape), With<Paddle>>,
+    gutters: Query<(&Position, &Shape), With<Gutter>>,
+    mut rally: ResMut<Rally>,
+    mut audio_events: EventWriter<GameAudio>,
 ) {
-    if let Ok((mut ball_velocity, ball_position, ball_shape)) = ball.get_single_mut() {
-        for (position, shape) in &other_things {
-            if let Some(collision) = collide_with_side(
+    if let Ok((mut ball_velocity, mut ball_position, ball_shape)) = ball.get_single_mut() {
+        for (paddle_position, shape) in &paddles {
+            if let Some((collision, depth)) = collide_with_side(
+                BoundingCircle::new(ball_position.0, ball_shape.0.x),
+                Aabb2d::new(paddle_position.0, shape.0 / 2.),
+            ) {
+                let normal = collision_normal(collision);
+
+                // Only steer the ball if it's still moving into the paddle.
+                // Without this check a ball resting against a paddle would
+                // flip back and forth every frame instead of settling.
+                if ball_velocity.0.dot(normal) < 0. {
+                    rally.hits += 1;
+                    // Each hit ramps the ball up a little so long rallies
+                    // get progressively harder to return.
+                    let speed = (ball_velocity.0.length() * RALLY_SPEED_UP).min(MAX_BALL_SPEED);
+
+                    // Where the ball hit the paddle, from -1 (bottom edge) to
+                    // 1 (top edge), steers the outgoing angle so players can
+                    // aim the ball instead of always bouncing it straight back.
+                    let t = ((ball_position.0.y - paddle_position.0.y) / (PADDLE_HEIGHT / 2.))
+                        .clamp(-1., 1.);
+                    let angle = t * MAX_BOUNCE_ANGLE;
+                    // Send the ball back toward the opposite side of the
+                    // court from whichever paddle it hit, regardless of
+                    // which edge of the paddle it grazed.
+                    let outgoing_x = if paddle_position.0.x < 0. { 1. } else { -1. };
+
+                    ball_velocity.0 = Vec2::new(outgoing_x * angle.cos(), angle.sin()) * speed;
+                    audio_events.send(GameAudio::PaddleHit { ball_speed: speed });
+                }
+
+                // Push the ball out of the paddle so it doesn't keep
+                // overlapping (and re-triggering) on the next frame.
+                ball_position.0 += normal * depth;
+            }
+        }
+
+        for (position, shape) in &gutters {
+            if let Some((collision, depth)) = collide_with_side(
                 BoundingCircle::new(ball_position.0, ball_shape.0.x),
                 Aabb2d::new(position.0, shape.0 / 2.),
             ) {
-                match collision {
-                    Collision::Left => {
-                        ball_velocity.0.x *= -1.;
-                    }
-                    Collision::Right => {
-                        ball_velocity.0.x *= -1.;
-                    }
-                    Collision::Top => {
-                        ball_velocity.0.y *= -1.;
-                    }
-                    Collision::Bottom => {
-                        ball_velocity.0.y *= -1.;
+                let normal = collision_normal(collision);
+
+                // Only reflect if the ball is still moving into the surface.
+                // Without this check a ball resting against a wall would
+                // flip back and forth every frame instead of settling.
+                if ball_velocity.0.dot(normal) < 0. {
+                    match collision {
+                        Collision::Left | Collision::Right => ball_velocity.0.x *= -1.,
+                        Collision::Top | Collision::Bottom => ball_velocity.0.y *= -1.,
                     }
+                    audio_events.send(GameAudio::WallBounce);
                 }
+
+                // Push the ball out of the wall so it doesn't keep
+                // overlapping (and re-triggering) on the next frame.
+                ball_position.0 += normal * depth;
             }
         }
     }
 }
+// `top_gutter_entity`/`bottom_gutter_entity` are only read when the `rapier`
+// feature is on.
+#[allow(unused_variables)]
 fn spawn_gutters(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -305,23 +607,37 @@ fn spawn_gutters(
         let mesh_handle = meshes.add(mesh);
         let material_handle = materials.add(material);
 
-        commands.spawn((
-            top_gutter,
-            MaterialMesh2dBundle {
-                mesh: mesh_handle.clone().into(),
-                material: material_handle.clone(),
-                ..default()
-            },
-        ));
-
-        commands.spawn((
-            bottom_gutter,
-            MaterialMesh2dBundle {
-                mesh: mesh_handle.into(),
-                material: material_handle.clone(),
-                ..default()
-            },
-        ));
+        let top_gutter_entity = commands
+            .spawn((
+                top_gutter,
+                MaterialMesh2dBundle {
+                    mesh: mesh_handle.clone().into(),
+                    material: material_handle.clone(),
+                    ..default()
+                },
+            ))
+            .id();
+
+        let bottom_gutter_entity = commands
+            .spawn((
+                bottom_gutter,
+                MaterialMesh2dBundle {
+                    mesh: mesh_handle.into(),
+                    material: material_handle.clone(),
+                    ..default()
+                },
+            ))
+            .id();
+
+        #[cfg(feature = "rapier")]
+        {
+            commands
+                .entity(top_gutter_entity)
+                .insert(physics_rapier::gutter_physics(window_width));
+            commands
+                .entity(bottom_gutter_entity)
+                .insert(physics_rapier::gutter_physics(window_width));
+        }
     }
 }
 fn handle_player_input(
@@ -347,17 +663,19 @@ fn move_paddles(
         let max_y = window_height / 2. - GUTTER_HEIGHT - PADDLE_HEIGHT / 2.;
 
         for (mut position, velocity) in &mut paddle {
-            let new_position = position.0 + velocity.0 * PADDLE_SPEED;
+            let new_position = position.0 + velocity.0 * PADDLE_SPEED * TIME_STEP;
             if new_position.y.abs() < max_y {
                 position.0 = new_position;
             }
         }
     }
 }
+#[cfg(not(feature = "rapier"))]
 fn detect_scoring(
     mut ball: Query<&mut Position, With<Ball>>,
     window: Query<&Window>,
     mut events: EventWriter<Scored>,
+    mut audio_events: EventWriter<GameAudio>,
 ) {
     if let Ok(window) = window.get_single() {
         let window_width = window.resolution.width();
@@ -366,43 +684,152 @@ fn detect_scoring(
             // Here we write the events using our EventWriter
             if ball.0.x > window_width / 2. {
                 events.send(Scored(Scorer::Ai));
+                audio_events.send(GameAudio::Score);
             } else if ball.0.x < -window_width / 2. {
                 events.send(Scored(Scorer::Player));
+                audio_events.send(GameAudio::Score);
             }
         }
     }
 }
-fn reset_ball(
+// Centers and freezes the ball at the start of a serve; `launch_ball` sends
+// it on its way once `ServeTimer` finishes.
+fn enter_serving(
     mut ball: Query<(&mut Position, &mut Velocity), With<Ball>>,
-    mut events: EventReader<Scored>,
+    mut serve_timer: ResMut<ServeTimer>,
+    mut rally: ResMut<Rally>,
 ) {
-    for event in events.read() {
-        if let Ok((mut position, mut velocity)) = ball.get_single_mut() {
-            match event.0 {
-                Scorer::Ai => {
-                    position.0 = Vec2::new(0., 0.);
-                    velocity.0 = Vec2::new(-1., 1.);
-                }
-                Scorer::Player => {
-                    position.0 = Vec2::new(0., 0.);
-                    velocity.0 = Vec2::new(1., 1.);
-                }
-            }
+    if let Ok((mut position, mut velocity)) = ball.get_single_mut() {
+        position.0 = Vec2::new(0., 0.);
+        velocity.0 = Vec2::new(0., 0.);
+    }
+    serve_timer.0.reset();
+    rally.hits = 0;
+}
+
+fn launch_ball(
+    time: Res<Time>,
+    mut serve_timer: ResMut<ServeTimer>,
+    mut ball: Query<&mut Velocity, With<Ball>>,
+    last_scorer: Res<LastScorer>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    serve_timer.0.tick(time.delta());
+
+    if serve_timer.0.finished() {
+        if let Ok(mut velocity) = ball.get_single_mut() {
+            velocity.0 = match last_scorer.0 {
+                Some(Scorer::Ai) => Vec2::new(-1., 1.),
+                _ => Vec2::new(1., 1.),
+            } * BALL_SPEED;
         }
+        next_state.set(AppState::Playing);
     }
 }
 
-fn update_score(mut score: ResMut<Score>, mut events: EventReader<Scored>) {
+fn update_score(
+    mut score: ResMut<Score>,
+    mut last_scorer: ResMut<LastScorer>,
+    mut events: EventReader<Scored>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
     for event in events.read() {
         match event.0 {
             Scorer::Ai => score.ai += 1,
             Scorer::Player => score.player += 1,
         }
+        last_scorer.0 = Some(event.0);
+
+        if score.ai >= WINNING_SCORE || score.player >= WINNING_SCORE {
+            next_state.set(AppState::GameOver);
+        } else {
+            next_state.set(AppState::Serving);
+        }
     }
 
     println!("Score: {} - {}", score.player, score.ai);
 }
 
+fn handle_main_menu_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        next_state.set(AppState::Serving);
+    }
+}
+
+fn handle_game_over_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut score: ResMut<Score>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        *score = Score::default();
+        next_state.set(AppState::MainMenu);
+    }
+}
+
+fn spawn_main_menu_screen(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "PONG\n\nPress Space to Start",
+            TextStyle {
+                font_size: 48.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_text_justify(JustifyText::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(40.0),
+            width: Val::Percent(100.0),
+            ..default()
+        }),
+        MainMenuUi,
+    ));
+}
+
+fn despawn_main_menu_screen(mut commands: Commands, ui: Query<Entity, With<MainMenuUi>>) {
+    for entity in &ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn spawn_game_over_screen(mut commands: Commands, score: Res<Score>) {
+    let winner = if score.player >= WINNING_SCORE {
+        "Player"
+    } else {
+        "AI"
+    };
+
+    commands.spawn((
+        TextBundle::from_section(
+            format!("{winner} wins!\n\nPress Space to play again"),
+            TextStyle {
+                font_size: 48.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_text_justify(JustifyText::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Percent(40.0),
+            width: Val::Percent(100.0),
+            ..default()
+        }),
+        GameOverUi,
+    ));
+}
+
+fn despawn_game_over_screen(mut commands: Commands, ui: Query<Entity, With<GameOverUi>>) {
+    for entity in &ui {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 fn update_scoreboard(
     mut player_score: Query<&mut Text, With<PlayerScore>>,
     mut ai_score: Query<&mut Text, (With<AiScore>, Without<PlayerScore>)>,
@@ -419,6 +846,18 @@ fn update_scoreboard(
     }
 }
 
+fn update_rally_text(mut rally_text: Query<&mut Text, With<RallyText>>, rally: Res<Rally>) {
+    if rally.is_changed() {
+        if let Ok(mut rally_text) = rally_text.get_single_mut() {
+            rally_text.sections[0].value = if rally.hits > 0 {
+                format!("Rally: {}", rally.hits)
+            } else {
+                String::new()
+            };
+        }
+    }
+}
+
 fn spawn_scoreboard(mut commands: Commands) {
     commands.spawn((
         // Create a TextBundle that has a Text with a
@@ -463,6 +902,26 @@ fn spawn_scoreboard(mut commands: Commands) {
         }),
         AiScore,
     ));
+
+    // The current rally length, shown between the two scores.
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 24.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_text_justify(JustifyText::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(15.0),
+            width: Val::Percent(100.0),
+            ..default()
+        }),
+        RallyText,
+    ));
 }
 fn move_ai(
     mut ai: Query<(&mut Velocity, &Position), With<Ai>>,
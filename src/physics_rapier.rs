@@ -0,0 +1,143 @@
+// Experimental `bevy_rapier2d`-backed collision pipeline, enabled with the
+// `rapier` cargo feature (requires an optional `bevy_rapier2d` dependency
+// declared against that feature). It replaces the bespoke penetration-depth
+// resolution in `handle_collisions`/`detect_scoring` with real rigid-body
+// simulation: the ball becomes a dynamic body with CCD so fast rallies can't
+// tunnel through the gutters, and restitution handles perfectly elastic
+// bounces for free.
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    AppState, Ball, GameAudio, Gutter, Paddle, Scored, Scorer, Velocity as GameVelocity,
+    BALL_SIZE, GUTTER_HEIGHT, PADDLE_HEIGHT, PADDLE_WIDTH,
+};
+
+pub struct RapierPongPlugin;
+
+impl Plugin for RapierPongPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(
+            RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(1.).in_fixed_schedule(),
+        )
+        .insert_resource(RapierConfiguration {
+            gravity: Vec2::ZERO,
+            ..RapierConfiguration::new(1.)
+        })
+        .add_systems(Startup, spawn_goals)
+        .add_systems(
+            FixedUpdate,
+            sync_ball_velocity
+                .before(PhysicsSet::SyncBackend)
+                .run_if(in_state(AppState::Playing)),
+        )
+        .add_systems(
+            FixedUpdate,
+            read_collision_events.run_if(in_state(AppState::Playing)),
+        );
+    }
+}
+
+// Marks an invisible sensor collider beyond each gutter that scores a point
+// for whoever the ball passed, mirroring `detect_scoring`'s window-edge check.
+#[derive(Component)]
+struct Goal(Scorer);
+
+pub fn ball_physics() -> impl Bundle {
+    (
+        RigidBody::Dynamic,
+        Collider::ball(BALL_SIZE),
+        Restitution::coefficient(1.),
+        Ccd::enabled(),
+        ActiveEvents::COLLISION_EVENTS,
+        Velocity::zero(),
+    )
+}
+
+pub fn paddle_physics() -> impl Bundle {
+    (
+        RigidBody::KinematicPositionBased,
+        Collider::cuboid(PADDLE_WIDTH / 2., PADDLE_HEIGHT / 2.),
+        Restitution::coefficient(1.),
+        ActiveEvents::COLLISION_EVENTS,
+    )
+}
+
+pub fn gutter_physics(width: f32) -> impl Bundle {
+    (
+        RigidBody::Fixed,
+        Collider::cuboid(width / 2., GUTTER_HEIGHT / 2.),
+        Restitution::coefficient(1.),
+    )
+}
+
+fn spawn_goals(mut commands: Commands, window: Query<&Window>) {
+    if let Ok(window) = window.get_single() {
+        let half_height = window.resolution.height() / 2.;
+        let half_width = window.resolution.width() / 2.;
+
+        // A point scored past the right edge is a point for the AI (the
+        // player's paddle sits on the right), matching `detect_scoring`.
+        commands.spawn((
+            Goal(Scorer::Ai),
+            RigidBody::Fixed,
+            Sensor,
+            Collider::cuboid(1., half_height),
+            ActiveEvents::COLLISION_EVENTS,
+            TransformBundle::from_transform(Transform::from_xyz(half_width, 0., 0.)),
+        ));
+        commands.spawn((
+            Goal(Scorer::Player),
+            RigidBody::Fixed,
+            Sensor,
+            Collider::cuboid(1., half_height),
+            ActiveEvents::COLLISION_EVENTS,
+            TransformBundle::from_transform(Transform::from_xyz(-half_width, 0., 0.)),
+        ));
+    }
+}
+
+// `launch_ball`/`enter_serving` only know about the bespoke `Velocity`
+// component (shared with the paddles), so mirror it into Rapier's own
+// velocity every tick, just before the physics step reads it.
+fn sync_ball_velocity(mut ball: Query<(&GameVelocity, &mut Velocity), With<Ball>>) {
+    if let Ok((game_velocity, mut rapier_velocity)) = ball.get_single_mut() {
+        rapier_velocity.linvel = game_velocity.0;
+    }
+}
+
+fn read_collision_events(
+    mut collisions: EventReader<CollisionEvent>,
+    balls: Query<(), With<Ball>>,
+    paddles: Query<(), With<Paddle>>,
+    gutters: Query<(), With<Gutter>>,
+    goals: Query<&Goal>,
+    mut scored: EventWriter<Scored>,
+    mut audio_events: EventWriter<GameAudio>,
+) {
+    for event in collisions.read() {
+        let CollisionEvent::Started(a, b, _) = event else {
+            continue;
+        };
+
+        let (ball, other) = if balls.contains(*a) {
+            (*a, *b)
+        } else if balls.contains(*b) {
+            (*b, *a)
+        } else {
+            continue;
+        };
+        let _ = ball;
+
+        if paddles.contains(other) {
+            audio_events.send(GameAudio::PaddleHit {
+                ball_speed: crate::BALL_SPEED,
+            });
+        } else if gutters.contains(other) {
+            audio_events.send(GameAudio::WallBounce);
+        } else if let Ok(goal) = goals.get(other) {
+            scored.send(Scored(goal.0));
+            audio_events.send(GameAudio::Score);
+        }
+    }
+}